@@ -1,6 +1,6 @@
 //! Async server example using CertNode verification.
 
-use certnode::{verify_receipt, Receipt, JwksManager};
+use certnode::{verify_receipt, FetchPolicy, Receipt, JwksManager};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
@@ -31,8 +31,18 @@ struct VerificationServer {
 
 impl VerificationServer {
     fn new() -> Self {
+        let jwks_manager = JwksManager::new(Duration::from_secs(300));
+        // `jwks_url` in VerifyRequest comes straight from the request body,
+        // so it's untrusted input: without a fetch policy, an attacker could
+        // point it at an internal address (e.g. the cloud metadata service)
+        // and have this server fetch it on their behalf (SSRF). Requiring
+        // HTTPS and blocking private/loopback/link-local addresses closes
+        // that off; tighten further with `.allow_prefixes(...)` if the set
+        // of trusted issuers is known ahead of time.
+        jwks_manager.set_fetch_policy(FetchPolicy::new().require_https().block_private_ips());
+
         Self {
-            jwks_manager: Arc::new(JwksManager::new(Duration::from_secs(300))),
+            jwks_manager: Arc::new(jwks_manager),
         }
     }
 
@@ -31,6 +31,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
                 kid: Some("test-key".to_string()),
                 alg: Some("ES256".to_string()),
+                use_: None,
+                key_ops: vec![],
             }
         ],
     };
@@ -82,6 +84,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             println!("  Algorithm: {}", alg);
                         }
                     }
+                    Jwk::Rsa { kid, alg, .. } => {
+                        println!("  Type: RSA");
+                        if let Some(kid) = kid {
+                            println!("  Kid: {}", kid);
+                        }
+                        if let Some(alg) = alg {
+                            println!("  Algorithm: {}", alg);
+                        }
+                    }
                 }
             }
             Err(e) => {
@@ -18,6 +18,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
                 kid: Some("ec-key-1".to_string()),
                 alg: Some("ES256".to_string()),
+                use_: None,
+                key_ops: vec![],
             },
             Jwk::Okp {
                 kty: "OKP".to_string(),
@@ -25,6 +27,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_string(),
                 kid: Some("ed25519-key-1".to_string()),
                 alg: Some("EdDSA".to_string()),
+                use_: None,
+                key_ops: vec![],
             }
         ],
     };
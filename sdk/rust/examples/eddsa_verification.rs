@@ -31,6 +31,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_string(),
                 kid: Some("ed25519-key".to_string()),
                 alg: Some("EdDSA".to_string()),
+                use_: None,
+                key_ops: vec![],
             }
         ],
     };
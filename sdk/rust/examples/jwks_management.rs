@@ -21,6 +21,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
                 kid: Some("test-ec-key".to_string()),
                 alg: Some("ES256".to_string()),
+                use_: None,
+                key_ops: vec![],
             },
             Jwk::Okp {
                 kty: "OKP".to_string(),
@@ -28,6 +30,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_string(),
                 kid: Some("test-ed25519-key".to_string()),
                 alg: Some("EdDSA".to_string()),
+                use_: None,
+                key_ops: vec![],
             }
         ],
     };
@@ -164,7 +164,9 @@ async fn thumbprint_command(matches: &clap::ArgMatches) -> Result<(), Box<dyn st
         match certnode::jwk_thumbprint(key) {
             Ok(thumbprint) => {
                 let kid = match key {
-                    certnode::Jwk::Ec { kid, .. } | certnode::Jwk::Okp { kid, .. } => kid,
+                    certnode::Jwk::Ec { kid, .. }
+                    | certnode::Jwk::Okp { kid, .. }
+                    | certnode::Jwk::Rsa { kid, .. } => kid,
                 };
 
                 println!("Key {}: {}", i + 1, thumbprint);
@@ -185,6 +187,12 @@ async fn thumbprint_command(matches: &clap::ArgMatches) -> Result<(), Box<dyn st
                             println!("  algorithm: {}", alg);
                         }
                     }
+                    certnode::Jwk::Rsa { alg, .. } => {
+                        println!("  type: RSA");
+                        if let Some(alg) = alg {
+                            println!("  algorithm: {}", alg);
+                        }
+                    }
                 }
                 println!();
             }
@@ -26,6 +26,13 @@ pub enum CertNodeError {
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    /// A JWKS fetch target was rejected by a `FetchPolicy`, e.g. a
+    /// non-HTTPS URL, a host outside the allowlist, or one resolving to a
+    /// private/loopback/link-local address (only with jwks-fetch feature)
+    #[cfg(feature = "jwks-fetch")]
+    #[error("Fetch blocked by policy: {0}")]
+    FetchBlocked(String),
+
     /// Generic error for other cases
     #[error("CertNode error: {0}")]
     Other(String),
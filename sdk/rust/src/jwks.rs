@@ -1,12 +1,271 @@
 //! JWKS management for CertNode SDK.
 
 use crate::{CertNodeError, Jwk, Jwks, Result};
+use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(feature = "jwks-fetch")]
+use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
+/// Maximum HTTP redirects followed while fetching a JWKS, re-validating the
+/// target against the active [`FetchPolicy`] at every hop.
+#[cfg(feature = "jwks-fetch")]
+const MAX_REDIRECTS: u8 = 5;
+
+/// Opt-in policy restricting which URLs [`JwksManager::fetch_from_url`] (or
+/// [`fetch_jwks_with_policy`]) is allowed to fetch, to guard against SSRF
+/// when a `jwks_url` comes from an untrusted source.
+///
+/// Disabled by default (`FetchPolicy::default()` allows any URL); enable
+/// only the checks you need via the builder methods.
+#[cfg(feature = "jwks-fetch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+#[derive(Debug, Clone, Default)]
+pub struct FetchPolicy {
+    require_https: bool,
+    allowed_prefixes: Vec<String>,
+    block_private_ips: bool,
+}
+
+#[cfg(feature = "jwks-fetch")]
+impl FetchPolicy {
+    /// Start with no restrictions; opt in via the other builder methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the URL scheme to be `https`.
+    pub fn require_https(mut self) -> Self {
+        self.require_https = true;
+        self
+    }
+
+    /// Restrict fetches to URLs that start with one of `prefixes`, e.g.
+    /// `"https://issuer.example.com/"`.
+    pub fn allow_prefixes<I, S>(mut self, prefixes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_prefixes
+            .extend(prefixes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Resolve the URL's host and reject it if any resolved address falls in
+    /// a private, loopback, link-local, or unspecified IP range.
+    pub fn block_private_ips(mut self) -> Self {
+        self.block_private_ips = true;
+        self
+    }
+
+    /// Check `url` against this policy, resolving its host if
+    /// [`block_private_ips`](Self::block_private_ips) is enabled.
+    ///
+    /// Returns [`CertNodeError::FetchBlocked`] if the URL violates the
+    /// policy, rather than the `NetworkError` a genuine connection failure
+    /// would produce.
+    ///
+    /// When [`block_private_ips`](Self::block_private_ips) is enabled, the
+    /// addresses that passed resolution are returned so the caller can pin
+    /// the actual connection to them instead of re-resolving the host a
+    /// second time, which would reopen a DNS-rebinding window between this
+    /// check and the request. An empty vec means the policy didn't resolve
+    /// the host (no pinning needed/possible).
+    async fn enforce(&self, url: &str) -> Result<Vec<SocketAddr>> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| CertNodeError::FetchBlocked(format!("Invalid URL {}: {}", url, e)))?;
+
+        if self.require_https && parsed.scheme() != "https" {
+            return Err(CertNodeError::FetchBlocked(format!(
+                "scheme \"{}\" is not https",
+                parsed.scheme()
+            )));
+        }
+
+        if !self.allowed_prefixes.is_empty()
+            && !self
+                .allowed_prefixes
+                .iter()
+                .any(|prefix| url.starts_with(prefix.as_str()))
+        {
+            return Err(CertNodeError::FetchBlocked(format!(
+                "{} is not in the allowed URL prefix list",
+                url
+            )));
+        }
+
+        if self.block_private_ips {
+            let host = parsed
+                .host_str()
+                .ok_or_else(|| CertNodeError::FetchBlocked(format!("{} has no host", url)))?;
+            let port = parsed.port_or_known_default().unwrap_or(443);
+
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|e| {
+                    CertNodeError::FetchBlocked(format!(
+                        "DNS resolution failed for {}: {}",
+                        host, e
+                    ))
+                })?
+                .collect();
+
+            for addr in &addrs {
+                if is_disallowed_ip(addr.ip()) {
+                    return Err(CertNodeError::FetchBlocked(format!(
+                        "{} resolves to disallowed address {}",
+                        host,
+                        addr.ip()
+                    )));
+                }
+            }
+
+            return Ok(addrs);
+        }
+
+        Ok(Vec::new())
+    }
+}
+
+/// Send a GET request for `url` using `client`, pinning the connection to
+/// `pinned_addrs` (the addresses a [`FetchPolicy`] already validated) when
+/// non-empty so the request can't be silently re-resolved to a different,
+/// disallowed address between the check and the connection.
+#[cfg(feature = "jwks-fetch")]
+async fn send_validated(
+    client: &reqwest::Client,
+    url: &str,
+    pinned_addrs: &[SocketAddr],
+) -> Result<reqwest::Response> {
+    if pinned_addrs.is_empty() {
+        return Ok(client.get(url).timeout(Duration::from_secs(30)).send().await?);
+    }
+
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| CertNodeError::FetchBlocked(format!("Invalid URL {}: {}", url, e)))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| CertNodeError::FetchBlocked(format!("{} has no host", url)))?;
+
+    let mut builder = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+    for addr in pinned_addrs {
+        builder = builder.resolve(host, *addr);
+    }
+    let pinned_client = builder
+        .build()
+        .map_err(|e| CertNodeError::NetworkError(e.to_string()))?;
+
+    Ok(pinned_client
+        .get(url)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await?)
+}
+
+/// Fetch `url` through `client`, re-validating against `policy` and pinning
+/// the connection at every redirect hop instead of letting the underlying
+/// HTTP client follow redirects unchecked (the classic SSRF-via-redirect
+/// bypass: a URL that passes policy can still respond with a `3xx` pointing
+/// at a private or disallowed address).
+///
+/// `client` must be built with `.redirect(reqwest::redirect::Policy::none())`
+/// for this guard to see (and re-validate) redirects at all; otherwise the
+/// client will have already followed them before this function runs.
+#[cfg(feature = "jwks-fetch")]
+async fn fetch_with_redirect_guard(
+    client: &reqwest::Client,
+    policy: &FetchPolicy,
+    url: &str,
+) -> Result<reqwest::Response> {
+    let mut current = url.to_string();
+
+    for _ in 0..MAX_REDIRECTS {
+        let pinned_addrs = policy.enforce(&current).await?;
+        let response = send_validated(client, &current, &pinned_addrs).await?;
+
+        if !response.status().is_redirection() {
+            return Ok(response);
+        }
+
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                CertNodeError::NetworkError(format!(
+                    "Redirect from {} missing Location header",
+                    current
+                ))
+            })?;
+        let base = reqwest::Url::parse(&current)
+            .map_err(|e| CertNodeError::FetchBlocked(format!("Invalid URL {}: {}", current, e)))?;
+        let next = base.join(location).map_err(|e| {
+            CertNodeError::FetchBlocked(format!("Invalid redirect target {}: {}", location, e))
+        })?;
+        current = next.to_string();
+    }
+
+    Err(CertNodeError::FetchBlocked(format!(
+        "Too many redirects fetching {}",
+        url
+    )))
+}
+
+/// Whether `ip` falls in a private, loopback, link-local, or unspecified
+/// range that a JWKS fetch should never be allowed to reach.
+#[cfg(feature = "jwks-fetch")]
+fn is_disallowed_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+        }
+        IpAddr::V6(v6) => {
+            v6.is_loopback()
+                || v6.is_unspecified()
+                || (v6.segments()[0] & 0xfe00) == 0xfc00 // unique local, fc00::/7
+                || (v6.segments()[0] & 0xffc0) == 0xfe80 // link-local, fe80::/10
+        }
+    }
+}
+
+/// Default number of JWKS entries kept in the manager's LRU cache.
+const DEFAULT_CACHE_CAPACITY: usize = 32;
+
+/// Default minimum interval between background refreshes of the same URL
+/// under stale-while-revalidate, so a flaky or slow origin can't have its
+/// refresh re-triggered on every call while it's stale. See
+/// [`JwksManager::with_stale_while_revalidate_tiers`].
+#[cfg(feature = "jwks-fetch")]
+const DEFAULT_MIN_RENEW_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default minimum cooldown between reload attempts triggered by
+/// [`JwksManager::verify_with_reload`]'s unknown-kid path, so a forged or
+/// random `kid` can't force an unbounded stream of synchronous refetches.
+#[cfg(feature = "jwks-fetch")]
+const DEFAULT_RELOAD_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Cache key used for JWKS installed directly via [`JwksManager::set_from_object`],
+/// as opposed to ones fetched from a URL.
+const MANUAL_CACHE_KEY: &str = "\0manual";
+
 /// JWKS manager with caching and async support.
 ///
 /// Provides automatic JWKS fetching and caching with configurable TTL.
+/// Keeps one cache entry per source URL (plus one for JWKS installed
+/// directly via [`set_from_object`](JwksManager::set_from_object)), evicting
+/// the least-recently-used entry once `capacity` is exceeded.
+///
+/// Optionally supports stale-while-revalidate: once built with
+/// [`with_stale_while_revalidate`](JwksManager::with_stale_while_revalidate),
+/// an entry older than `ttl` but still within `ttl + stale_ttl` is served
+/// immediately while a background task refreshes it, instead of blocking the
+/// caller on a fetch.
+///
 /// Thread-safe and optimized for high-performance scenarios.
 ///
 /// # Examples
@@ -23,12 +282,45 @@ use std::time::{Duration, Instant};
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct JwksManager {
+    inner: Arc<JwksManagerInner>,
+}
+
+#[derive(Debug)]
+struct JwksManagerInner {
     ttl: Duration,
-    cache: Arc<RwLock<Option<CachedJwks>>>,
+    /// Extra grace window after `ttl` during which a stale entry is served
+    /// while a background refresh runs. Zero disables stale-while-revalidate:
+    /// an expired entry is refetched synchronously, as before.
+    stale_ttl: Duration,
+    /// Minimum interval between background refresh attempts for the same
+    /// URL, rate-limiting stale-while-revalidate so a repeatedly-failing or
+    /// slow origin doesn't get re-triggered on every call that observes a
+    /// stale entry.
+    #[cfg(feature = "jwks-fetch")]
+    min_renew_interval: RwLock<Duration>,
+    /// Minimum cooldown between reload attempts triggered by
+    /// [`JwksManager::verify_with_reload`]'s unknown-kid path.
+    #[cfg(feature = "jwks-fetch")]
+    reload_cooldown: RwLock<Duration>,
+    cache: RwLock<LruJwksCache>,
+    /// URLs with a background refresh currently in flight, so concurrent
+    /// callers don't each spawn their own refresh of the same URL.
+    refreshing: RwLock<HashSet<String>>,
+    /// Per-URL timestamp of the last background refresh attempt, gating
+    /// `min_renew_interval`.
+    #[cfg(feature = "jwks-fetch")]
+    last_refresh_attempt: RwLock<HashMap<String, Instant>>,
+    /// Per-URL timestamp of the last `verify_with_reload` reload attempt,
+    /// gating `reload_cooldown`.
+    #[cfg(feature = "jwks-fetch")]
+    last_reload_attempt: RwLock<HashMap<String, Instant>>,
     #[cfg(feature = "jwks-fetch")]
     client: reqwest::Client,
+    /// Opt-in SSRF guard applied to every fetch. Defaults to no restrictions.
+    #[cfg(feature = "jwks-fetch")]
+    policy: RwLock<FetchPolicy>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,36 +329,208 @@ struct CachedJwks {
     cached_at: Instant,
 }
 
+/// A bounded cache keyed by source URL (or [`MANUAL_CACHE_KEY`]), evicting
+/// the least-recently-used entry once `capacity` is exceeded.
+#[derive(Debug)]
+struct LruJwksCache {
+    capacity: usize,
+    entries: HashMap<String, CachedJwks>,
+    order: VecDeque<String>,
+}
+
+impl LruJwksCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<&CachedJwks> {
+        if self.entries.contains_key(key) {
+            self.touch(key);
+        }
+        self.entries.get(key)
+    }
+
+    fn insert(&mut self, key: String, value: CachedJwks) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            if let Some(lru_key) = self.order.pop_front() {
+                self.entries.remove(&lru_key);
+            }
+        }
+        self.touch(&key);
+        self.entries.insert(key, value);
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.entries.remove(key);
+        self.order.retain(|k| k != key);
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    fn touch(&mut self, key: &str) {
+        self.order.retain(|k| k != key);
+        self.order.push_back(key.to_string());
+    }
+}
+
 impl JwksManager {
-    /// Create a new JWKS manager with specified TTL.
+    /// Create a new JWKS manager with specified TTL and the default cache
+    /// capacity (32 entries). Stale-while-revalidate is disabled: once an
+    /// entry is older than `ttl`, fetches block on a synchronous refresh.
     ///
     /// # Arguments
     ///
     /// * `ttl` - Time-to-live for cached JWKS
     pub fn new(ttl: Duration) -> Self {
+        Self::with_capacity(ttl, DEFAULT_CACHE_CAPACITY)
+    }
+
+    /// Create a new JWKS manager with specified TTL and cache capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - Time-to-live for cached JWKS
+    /// * `capacity` - Maximum number of distinct JWKS sources to cache before
+    ///   the least-recently-used entry is evicted
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> Self {
+        Self::build(ttl, Duration::ZERO, capacity)
+    }
+
+    /// Create a new JWKS manager that serves stale entries immediately while
+    /// refreshing them in the background.
+    ///
+    /// An entry younger than `ttl` is fresh and returned as-is. An entry
+    /// between `ttl` and `ttl + stale_ttl` old is stale: it's still returned
+    /// immediately, but a background task is spawned (deduplicated per URL,
+    /// and rate-limited to a default minimum renew interval of 30 seconds)
+    /// to refetch it via [`fetch_from_url`](Self::fetch_from_url). An entry
+    /// older than `ttl + stale_ttl` is expired and blocks the caller on a
+    /// synchronous refetch, same as with stale-while-revalidate disabled.
+    ///
+    /// Background refreshes are spawned with [`tokio::spawn`] and require a
+    /// Tokio runtime to be running. Use
+    /// [`with_stale_while_revalidate_tiers`](Self::with_stale_while_revalidate_tiers)
+    /// to set the minimum renew interval explicitly.
+    ///
+    /// # Arguments
+    ///
+    /// * `ttl` - Time-to-live before an entry is considered stale
+    /// * `stale_ttl` - Additional grace period during which a stale entry is
+    ///   still served while it refreshes in the background
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn with_stale_while_revalidate(ttl: Duration, stale_ttl: Duration) -> Self {
+        Self::with_stale_while_revalidate_tiers(ttl, stale_ttl, DEFAULT_MIN_RENEW_INTERVAL)
+    }
+
+    /// Like [`with_stale_while_revalidate`](Self::with_stale_while_revalidate),
+    /// but with the minimum renew interval set explicitly instead of the
+    /// default 30 seconds: the rate limit on how often a background refresh
+    /// for the same URL can be started, regardless of how many calls observe
+    /// a stale entry in between.
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn with_stale_while_revalidate_tiers(
+        ttl: Duration,
+        stale_ttl: Duration,
+        min_renew_interval: Duration,
+    ) -> Self {
+        let manager = Self::build(ttl, stale_ttl, DEFAULT_CACHE_CAPACITY);
+        manager.set_min_renew_interval(min_renew_interval);
+        manager
+    }
+
+    /// Change the minimum interval between background refresh attempts for
+    /// the same URL under stale-while-revalidate. See
+    /// [`with_stale_while_revalidate_tiers`](Self::with_stale_while_revalidate_tiers).
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn set_min_renew_interval(&self, min_renew_interval: Duration) {
+        *self.inner.min_renew_interval.write().unwrap() = min_renew_interval;
+    }
+
+    /// Set the minimum cooldown between reload attempts triggered by
+    /// [`verify_with_reload`](Self::verify_with_reload)'s unknown-kid path,
+    /// to bound how often a forged or rotated-out `kid` can force a
+    /// synchronous refetch. Defaults to 30 seconds.
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn set_reload_cooldown(&self, cooldown: Duration) {
+        *self.inner.reload_cooldown.write().unwrap() = cooldown;
+    }
+
+    fn build(ttl: Duration, stale_ttl: Duration, capacity: usize) -> Self {
         Self {
-            ttl,
-            cache: Arc::new(RwLock::new(None)),
-            #[cfg(feature = "jwks-fetch")]
-            client: reqwest::Client::new(),
+            inner: Arc::new(JwksManagerInner {
+                ttl,
+                stale_ttl,
+                #[cfg(feature = "jwks-fetch")]
+                min_renew_interval: RwLock::new(DEFAULT_MIN_RENEW_INTERVAL),
+                #[cfg(feature = "jwks-fetch")]
+                reload_cooldown: RwLock::new(DEFAULT_RELOAD_COOLDOWN),
+                cache: RwLock::new(LruJwksCache::new(capacity)),
+                refreshing: RwLock::new(HashSet::new()),
+                #[cfg(feature = "jwks-fetch")]
+                last_refresh_attempt: RwLock::new(HashMap::new()),
+                #[cfg(feature = "jwks-fetch")]
+                last_reload_attempt: RwLock::new(HashMap::new()),
+                #[cfg(feature = "jwks-fetch")]
+                client: redirect_safe_client(),
+                #[cfg(feature = "jwks-fetch")]
+                policy: RwLock::new(FetchPolicy::default()),
+            }),
         }
     }
 
     /// Create a new JWKS manager with custom HTTP client.
+    ///
+    /// `client` should itself be built with
+    /// `.redirect(reqwest::redirect::Policy::none())`. A fetch policy
+    /// installed via [`set_fetch_policy`](Self::set_fetch_policy) is
+    /// re-validated at every redirect hop, but only if `client` doesn't
+    /// follow the redirect itself first.
     #[cfg(feature = "jwks-fetch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
     pub fn with_client(ttl: Duration, client: reqwest::Client) -> Self {
         Self {
-            ttl,
-            cache: Arc::new(RwLock::new(None)),
-            client,
+            inner: Arc::new(JwksManagerInner {
+                ttl,
+                stale_ttl: Duration::ZERO,
+                min_renew_interval: RwLock::new(DEFAULT_MIN_RENEW_INTERVAL),
+                reload_cooldown: RwLock::new(DEFAULT_RELOAD_COOLDOWN),
+                cache: RwLock::new(LruJwksCache::new(DEFAULT_CACHE_CAPACITY)),
+                refreshing: RwLock::new(HashSet::new()),
+                last_refresh_attempt: RwLock::new(HashMap::new()),
+                last_reload_attempt: RwLock::new(HashMap::new()),
+                client,
+                policy: RwLock::new(FetchPolicy::default()),
+            }),
         }
     }
 
+    /// Install a fetch policy restricting which URLs [`fetch_from_url`](Self::fetch_from_url)
+    /// is allowed to fetch. Replaces any previously installed policy.
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn set_fetch_policy(&self, policy: FetchPolicy) {
+        *self.inner.policy.write().unwrap() = policy;
+    }
+
     /// Fetch JWKS from URL with caching.
     ///
-    /// Returns cached JWKS if still fresh, otherwise fetches from the URL.
-    /// This method is thread-safe and can be called concurrently.
+    /// Returns the cached JWKS if it's fresh. If stale-while-revalidate is
+    /// enabled (see [`with_stale_while_revalidate`](Self::with_stale_while_revalidate))
+    /// and the cached entry is merely stale, it's still returned immediately
+    /// while a background refresh is kicked off. Otherwise fetches
+    /// synchronously. This method is thread-safe and can be called
+    /// concurrently.
     ///
     /// # Arguments
     ///
@@ -78,23 +542,33 @@ impl JwksManager {
     #[cfg(feature = "jwks-fetch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
     pub async fn fetch_from_url(&self, url: &str) -> Result<Jwks> {
-        // Check cache first (read lock)
-        {
-            let cache = self.cache.read().unwrap();
-            if let Some(cached) = cache.as_ref() {
-                if cached.cached_at.elapsed() < self.ttl {
-                    return Ok(cached.jwks.clone());
-                }
+        let cached = {
+            let mut cache = self.inner.cache.write().unwrap();
+            cache
+                .get(url)
+                .map(|cached| (cached.jwks.clone(), cached.cached_at.elapsed()))
+        };
+
+        if let Some((jwks, age)) = cached {
+            if age < self.inner.ttl {
+                return Ok(jwks);
+            }
+            if self.inner.stale_ttl > Duration::ZERO && age < self.inner.ttl + self.inner.stale_ttl
+            {
+                self.spawn_background_refresh(url.to_string());
+                return Ok(jwks);
             }
         }
 
-        // Fetch fresh JWKS
-        let response = self
-            .client
-            .get(url)
-            .timeout(Duration::from_secs(30))
-            .send()
-            .await?;
+        self.fetch_and_cache(url).await
+    }
+
+    /// Fetch fresh JWKS from `url` and store it in the cache, bypassing any
+    /// freshness check on the existing entry.
+    #[cfg(feature = "jwks-fetch")]
+    async fn fetch_and_cache(&self, url: &str) -> Result<Jwks> {
+        let policy = self.inner.policy.read().unwrap().clone();
+        let response = fetch_with_redirect_guard(&self.inner.client, &policy, url).await?;
 
         if !response.status().is_success() {
             return Err(CertNodeError::NetworkError(format!(
@@ -106,21 +580,167 @@ impl JwksManager {
 
         let jwks: Jwks = response.json().await?;
 
-        // Validate JWKS
         self.validate_jwks(&jwks)?;
 
-        // Update cache (write lock)
         {
-            let mut cache = self.cache.write().unwrap();
-            *cache = Some(CachedJwks {
-                jwks: jwks.clone(),
-                cached_at: Instant::now(),
-            });
+            let mut cache = self.inner.cache.write().unwrap();
+            cache.insert(
+                url.to_string(),
+                CachedJwks {
+                    jwks: jwks.clone(),
+                    cached_at: Instant::now(),
+                },
+            );
         }
 
         Ok(jwks)
     }
 
+    /// Whether enough time has passed since the last background refresh
+    /// attempt for `url` to start another one, per `min_renew_interval`
+    /// (see [`set_min_renew_interval`](Self::set_min_renew_interval)).
+    /// Records the attempt immediately so concurrent callers within the
+    /// window don't each think they're the first to retry.
+    #[cfg(feature = "jwks-fetch")]
+    fn should_attempt_refresh(&self, url: &str) -> bool {
+        let min_renew_interval = *self.inner.min_renew_interval.read().unwrap();
+        let mut last_attempt = self.inner.last_refresh_attempt.write().unwrap();
+        let now = Instant::now();
+        if let Some(last) = last_attempt.get(url) {
+            if now.duration_since(*last) < min_renew_interval {
+                return false;
+            }
+        }
+        last_attempt.insert(url.to_string(), now);
+        true
+    }
+
+    /// Spawn a background refresh of `url`'s cache entry, unless one is
+    /// already in flight or `min_renew_interval` hasn't elapsed since the
+    /// last attempt.
+    #[cfg(feature = "jwks-fetch")]
+    fn spawn_background_refresh(&self, url: String) {
+        if !self.should_attempt_refresh(&url) {
+            return;
+        }
+
+        {
+            let mut refreshing = self.inner.refreshing.write().unwrap();
+            if !refreshing.insert(url.clone()) {
+                return;
+            }
+        }
+
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let _ = manager.fetch_and_cache(&url).await;
+            manager.inner.refreshing.write().unwrap().remove(&url);
+        });
+    }
+
+    /// Evict the cached JWKS for `url`, forcing the next [`fetch_from_url`](Self::fetch_from_url)
+    /// call to fetch fresh, regardless of TTL. The URL-scoped counterpart to
+    /// [`clear_cache`](Self::clear_cache).
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn invalidate(&self, url: &str) {
+        let mut cache = self.inner.cache.write().unwrap();
+        cache.remove(url);
+    }
+
+    /// Get the cached JWKS for `url` if still fresh. The URL-scoped
+    /// counterpart to [`get_fresh`](Self::get_fresh).
+    ///
+    /// Returns `None` if no JWKS is cached for `url` or if the cache has
+    /// expired.
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn get_fresh_for(&self, url: &str) -> Option<Jwks> {
+        let mut cache = self.inner.cache.write().unwrap();
+        cache.get(url).and_then(|cached| {
+            if cached.cached_at.elapsed() < self.inner.ttl {
+                Some(cached.jwks.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Check if the cached JWKS for `url` is still fresh. The URL-scoped
+    /// counterpart to [`has_fresh_cache`](Self::has_fresh_cache).
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub fn has_fresh_cache_for(&self, url: &str) -> bool {
+        let mut cache = self.inner.cache.write().unwrap();
+        cache
+            .get(url)
+            .map_or(false, |cached| cached.cached_at.elapsed() < self.inner.ttl)
+    }
+
+    /// Verify a receipt against the JWKS at `url`, automatically refetching
+    /// once if the receipt's `kid` isn't found in the current cache.
+    ///
+    /// Guards against key rotation: if the signer has rotated to a key
+    /// fetched after our cache entry, a first pass that fails with "Key not
+    /// found" forces a fresh fetch (bypassing the TTL) and retries before
+    /// giving up.
+    ///
+    /// # Arguments
+    ///
+    /// * `receipt` - The receipt to verify
+    /// * `url` - URL to fetch JWKS from
+    #[cfg(feature = "jwks-fetch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+    pub async fn verify_with_reload(
+        &self,
+        receipt: &crate::Receipt,
+        url: &str,
+    ) -> Result<crate::VerifyResult> {
+        let jwks = self.fetch_from_url(url).await?;
+        let result = crate::verify_receipt(receipt, &jwks)?;
+        if result.ok || !Self::is_unknown_kid(&result) {
+            return Ok(result);
+        }
+
+        if !self.should_attempt_reload(url) {
+            return Ok(result);
+        }
+
+        self.invalidate(url);
+        let jwks = self.fetch_from_url(url).await?;
+        crate::verify_receipt(receipt, &jwks)
+    }
+
+    /// Whether enough time has passed since the last reload attempt for
+    /// `url` to issue another one, per [`set_reload_cooldown`](Self::set_reload_cooldown).
+    /// Records the attempt immediately so concurrent callers within the
+    /// cooldown window short-circuit without a network call.
+    fn should_attempt_reload(&self, url: &str) -> bool {
+        let reload_cooldown = *self.inner.reload_cooldown.read().unwrap();
+        let mut last_attempt = self.inner.last_reload_attempt.write().unwrap();
+        let now = Instant::now();
+        if let Some(last) = last_attempt.get(url) {
+            if now.duration_since(*last) < reload_cooldown {
+                return false;
+            }
+        }
+        last_attempt.insert(url.to_string(), now);
+        true
+    }
+
+    /// Whether a failed [`crate::VerifyResult`] was caused by a `kid` that
+    /// isn't present in the JWKS at all (as opposed to a `kid` that matched a
+    /// key disqualified by [`crate::check_key_constraints`], or some other
+    /// failure reason). Only a genuinely absent `kid` can be fixed by
+    /// refetching, so this must not match the disqualification reasons
+    /// produced when a key is present but not permitted to verify.
+    fn is_unknown_kid(result: &crate::VerifyResult) -> bool {
+        result
+            .reason
+            .as_deref()
+            .map_or(false, |reason| reason.starts_with("Key not found in JWKS"))
+    }
+
     /// Set JWKS from object with validation.
     ///
     /// # Arguments
@@ -129,22 +749,26 @@ impl JwksManager {
     pub fn set_from_object(&self, jwks: Jwks) -> Result<()> {
         self.validate_jwks(&jwks)?;
 
-        let mut cache = self.cache.write().unwrap();
-        *cache = Some(CachedJwks {
-            jwks,
-            cached_at: Instant::now(),
-        });
+        let mut cache = self.inner.cache.write().unwrap();
+        cache.insert(
+            MANUAL_CACHE_KEY.to_string(),
+            CachedJwks {
+                jwks,
+                cached_at: Instant::now(),
+            },
+        );
 
         Ok(())
     }
 
     /// Get cached JWKS if still fresh.
     ///
-    /// Returns `None` if no JWKS is cached or if the cache has expired.
+    /// Returns `None` if no JWKS is cached or if the cache has expired. See
+    /// also the `jwks-fetch`-gated `get_fresh_for` for a URL-scoped variant.
     pub fn get_fresh(&self) -> Option<Jwks> {
-        let cache = self.cache.read().unwrap();
-        cache.as_ref().and_then(|cached| {
-            if cached.cached_at.elapsed() < self.ttl {
+        let mut cache = self.inner.cache.write().unwrap();
+        cache.get(MANUAL_CACHE_KEY).and_then(|cached| {
+            if cached.cached_at.elapsed() < self.inner.ttl {
                 Some(cached.jwks.clone())
             } else {
                 None
@@ -162,14 +786,17 @@ impl JwksManager {
     ///
     /// Returns a vector of key thumbprints or an error.
     pub fn thumbprints(&self, jwks: Option<&Jwks>) -> Result<Vec<String>> {
+        let owned;
         let jwks = if let Some(jwks) = jwks {
             jwks
         } else {
-            let cache = self.cache.read().unwrap();
-            let cached = cache
-                .as_ref()
-                .ok_or_else(|| CertNodeError::Other("No JWKS available".into()))?;
-            &cached.jwks
+            let mut cache = self.inner.cache.write().unwrap();
+            owned = cache
+                .get(MANUAL_CACHE_KEY)
+                .ok_or_else(|| CertNodeError::Other("No JWKS available".into()))?
+                .jwks
+                .clone();
+            &owned
         };
 
         let mut thumbprints = Vec::new();
@@ -226,25 +853,42 @@ impl JwksManager {
                     ));
                 }
             }
+            Jwk::Rsa { n, e, .. } => {
+                if n.is_empty() || e.is_empty() {
+                    return Err(CertNodeError::InvalidFormat(
+                        "RSA key missing modulus or exponent".into(),
+                    ));
+                }
+            }
         }
 
         Ok(())
     }
 
-    /// Clear the cache.
+    /// Clear every cached JWKS, for every source URL as well as any set via
+    /// [`set_from_object`](Self::set_from_object). See also the
+    /// `jwks-fetch`-gated `invalidate` to evict a single URL.
     ///
     /// Forces the next fetch operation to retrieve fresh JWKS.
     pub fn clear_cache(&self) {
-        let mut cache = self.cache.write().unwrap();
-        *cache = None;
+        let mut cache = self.inner.cache.write().unwrap();
+        cache.clear();
     }
 
-    /// Check if cache contains fresh JWKS.
+    /// Check if the JWKS set via [`set_from_object`](Self::set_from_object) is
+    /// still fresh. See also the `jwks-fetch`-gated `has_fresh_cache_for` for
+    /// a URL-scoped variant.
     pub fn has_fresh_cache(&self) -> bool {
-        let cache = self.cache.read().unwrap();
+        let mut cache = self.inner.cache.write().unwrap();
         cache
-            .as_ref()
-            .map_or(false, |cached| cached.cached_at.elapsed() < self.ttl)
+            .get(MANUAL_CACHE_KEY)
+            .map_or(false, |cached| cached.cached_at.elapsed() < self.inner.ttl)
+    }
+
+    /// Number of distinct JWKS sources currently cached (regardless of
+    /// freshness).
+    pub fn cache_len(&self) -> usize {
+        self.inner.cache.read().unwrap().entries.len()
     }
 }
 
@@ -275,13 +919,36 @@ impl JwksManager {
 #[cfg(feature = "jwks-fetch")]
 #[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
 pub async fn fetch_jwks(url: &str) -> Result<Jwks> {
-    let client = reqwest::Client::new();
+    fetch_jwks_with_policy(url, &FetchPolicy::default()).await
+}
 
-    let response = client
-        .get(url)
-        .timeout(Duration::from_secs(30))
-        .send()
-        .await?;
+/// Like [`fetch_jwks`], but checking `url` (and every redirect hop it leads
+/// to) against `policy`.
+///
+/// Returns [`CertNodeError::FetchBlocked`] without issuing any request if the
+/// URL violates the policy, e.g. a non-HTTPS scheme, a host outside an
+/// allowlist, or one resolving to a private/loopback/link-local address.
+/// Redirects are followed manually, re-validating the target against
+/// `policy` at each hop, so a host that's allowed but responds with a `3xx`
+/// to a disallowed address can't be used to bypass the policy.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use certnode::jwks::{fetch_jwks_with_policy, FetchPolicy};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let policy = FetchPolicy::new().require_https().block_private_ips();
+/// let jwks = fetch_jwks_with_policy("https://api.certnode.io/.well-known/jwks.json", &policy).await?;
+/// println!("Fetched {} keys", jwks.keys.len());
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "jwks-fetch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwks-fetch")))]
+pub async fn fetch_jwks_with_policy(url: &str, policy: &FetchPolicy) -> Result<Jwks> {
+    let client = redirect_safe_client();
+    let response = fetch_with_redirect_guard(&client, policy, url).await?;
 
     if !response.status().is_success() {
         return Err(CertNodeError::NetworkError(format!(
@@ -301,6 +968,18 @@ pub async fn fetch_jwks(url: &str) -> Result<Jwks> {
     Ok(jwks)
 }
 
+/// Build the HTTP client used for JWKS fetches, with automatic redirect
+/// following disabled so [`fetch_with_redirect_guard`] can re-validate each
+/// hop against the active [`FetchPolicy`] instead of the client silently
+/// following a redirect to a disallowed address.
+#[cfg(feature = "jwks-fetch")]
+fn redirect_safe_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("building a reqwest client with no custom TLS/proxy config should never fail")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,6 +1004,8 @@ mod tests {
                 y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
                 kid: Some("test-key".to_string()),
                 alg: Some("ES256".to_string()),
+                use_: None,
+                key_ops: vec![],
             }],
         };
 
@@ -349,6 +1030,8 @@ mod tests {
                     y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
                     kid: Some("test-key".to_string()),
                     alg: Some("ES256".to_string()),
+                    use_: None,
+                    key_ops: vec![],
                 },
                 Jwk::Okp {
                     kty: "OKP".to_string(),
@@ -356,6 +1039,8 @@ mod tests {
                     x: "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo".to_string(),
                     kid: Some("ed25519-key".to_string()),
                     alg: Some("EdDSA".to_string()),
+                    use_: None,
+                    key_ops: vec![],
                 },
             ],
         };
@@ -382,6 +1067,8 @@ mod tests {
                 y: "test".to_string(),
                 kid: None,
                 alg: None,
+                use_: None,
+                key_ops: vec![],
             }],
         };
         assert!(manager.validate_jwks(&invalid_jwks).is_err());
@@ -399,6 +1086,8 @@ mod tests {
                 y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
                 kid: Some("test-key".to_string()),
                 alg: Some("ES256".to_string()),
+                use_: None,
+                key_ops: vec![],
             }],
         };
 
@@ -408,4 +1097,159 @@ mod tests {
         manager.clear_cache();
         assert!(!manager.has_fresh_cache());
     }
-}
\ No newline at end of file
+
+    fn dummy_cached_jwks() -> CachedJwks {
+        CachedJwks {
+            jwks: Jwks {
+                keys: vec![Jwk::Ec {
+                    kty: "EC".to_string(),
+                    crv: "P-256".to_string(),
+                    x: "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU".to_string(),
+                    y: "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0".to_string(),
+                    kid: Some("test-key".to_string()),
+                    alg: Some("ES256".to_string()),
+                    use_: None,
+                    key_ops: vec![],
+                }],
+            },
+            cached_at: Instant::now(),
+        }
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[test]
+    fn test_get_fresh_for_and_has_fresh_cache_for_are_url_scoped() {
+        let manager = JwksManager::new(Duration::from_secs(300));
+        assert!(!manager.has_fresh_cache_for("https://issuer.example.com/jwks.json"));
+        assert!(manager
+            .get_fresh_for("https://issuer.example.com/jwks.json")
+            .is_none());
+
+        {
+            let mut cache = manager.inner.cache.write().unwrap();
+            cache.insert(
+                "https://issuer.example.com/jwks.json".to_string(),
+                dummy_cached_jwks(),
+            );
+        }
+
+        assert!(manager.has_fresh_cache_for("https://issuer.example.com/jwks.json"));
+        assert!(manager
+            .get_fresh_for("https://issuer.example.com/jwks.json")
+            .is_some());
+        // The manual-cache slot is untouched by URL-scoped inserts.
+        assert!(!manager.has_fresh_cache());
+    }
+
+    #[test]
+    fn test_lru_cache_evicts_least_recently_used() {
+        let mut cache = LruJwksCache::new(2);
+        cache.insert("https://a".to_string(), dummy_cached_jwks());
+        cache.insert("https://b".to_string(), dummy_cached_jwks());
+        // Touch "a" so "b" becomes the least-recently-used entry.
+        assert!(cache.get("https://a").is_some());
+
+        cache.insert("https://c".to_string(), dummy_cached_jwks());
+
+        assert!(cache.get("https://a").is_some());
+        assert!(cache.get("https://b").is_none());
+        assert!(cache.get("https://c").is_some());
+    }
+
+    #[test]
+    fn test_manager_cache_len_evicts_over_capacity() {
+        let manager = JwksManager::with_capacity(Duration::from_secs(300), 1);
+
+        manager
+            .set_from_object(dummy_cached_jwks().jwks)
+            .unwrap();
+        assert_eq!(manager.cache_len(), 1);
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[test]
+    fn test_should_attempt_refresh_respects_min_renew_interval() {
+        let manager = JwksManager::new(Duration::from_secs(300));
+        manager.set_min_renew_interval(Duration::from_secs(60));
+
+        assert!(manager.should_attempt_refresh("https://issuer.example.com/jwks.json"));
+        // Immediately retrying the same URL is within the rate limit window.
+        assert!(!manager.should_attempt_refresh("https://issuer.example.com/jwks.json"));
+        // A different URL has its own independent rate limit.
+        assert!(manager.should_attempt_refresh("https://other.example.com/jwks.json"));
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[test]
+    fn test_should_attempt_reload_respects_cooldown() {
+        let manager = JwksManager::new(Duration::from_secs(300));
+        manager.set_reload_cooldown(Duration::from_secs(60));
+
+        assert!(manager.should_attempt_reload("https://issuer.example.com/jwks.json"));
+        // Immediately retrying the same URL is within the cooldown window.
+        assert!(!manager.should_attempt_reload("https://issuer.example.com/jwks.json"));
+        // A different URL has its own independent cooldown.
+        assert!(manager.should_attempt_reload("https://other.example.com/jwks.json"));
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[test]
+    fn test_is_unknown_kid_does_not_match_disqualification_reason() {
+        let absent = crate::VerifyResult::failed("Key not found in JWKS: test-key");
+        assert!(JwksManager::is_unknown_kid(&absent));
+
+        let disqualified =
+            crate::VerifyResult::failed("Key `use` is \"enc\", not \"sig\"".to_string());
+        assert!(!JwksManager::is_unknown_kid(&disqualified));
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[tokio::test]
+    async fn test_fetch_policy_rejects_non_https() {
+        let policy = FetchPolicy::new().require_https();
+        let err = policy.enforce("http://example.com/jwks.json").await;
+        assert!(matches!(err, Err(CertNodeError::FetchBlocked(_))));
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[tokio::test]
+    async fn test_fetch_policy_rejects_url_outside_allowlist() {
+        let policy = FetchPolicy::new().allow_prefixes(["https://issuer.example.com/"]);
+        let err = policy.enforce("https://evil.example.com/jwks.json").await;
+        assert!(matches!(err, Err(CertNodeError::FetchBlocked(_))));
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[tokio::test]
+    async fn test_fetch_policy_allows_url_inside_allowlist() {
+        let policy = FetchPolicy::new().allow_prefixes(["https://issuer.example.com/"]);
+        assert!(policy
+            .enforce("https://issuer.example.com/.well-known/jwks.json")
+            .await
+            .is_ok());
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[test]
+    fn test_is_disallowed_ip() {
+        assert!(is_disallowed_ip("127.0.0.1".parse().unwrap()));
+        assert!(is_disallowed_ip("10.0.0.1".parse().unwrap()));
+        assert!(is_disallowed_ip("169.254.0.5".parse().unwrap()));
+        assert!(is_disallowed_ip("::1".parse().unwrap()));
+        assert!(is_disallowed_ip("fe80::1".parse().unwrap()));
+        assert!(!is_disallowed_ip("93.184.216.34".parse().unwrap()));
+    }
+
+    #[cfg(feature = "jwks-fetch")]
+    #[tokio::test]
+    async fn test_fetch_policy_without_block_private_ips_does_not_resolve() {
+        // No DNS lookup is performed (and so nothing to pin), since nothing
+        // asked for block_private_ips.
+        let policy = FetchPolicy::new().require_https();
+        let pinned = policy
+            .enforce("https://issuer.example.com/.well-known/jwks.json")
+            .await
+            .unwrap();
+        assert!(pinned.is_empty());
+    }
+}